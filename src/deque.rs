@@ -0,0 +1,388 @@
+//! A double-ended queue implemented with a growable ring buffer, similar to
+//! [`std::collections::VecDeque`].
+
+use core::ptr::NonNull;
+
+use crate::{alloc_array_in, is_zst, try_alloc_array_in, Allocator, Global, TryReserveError};
+
+/// A double-ended queue implemented with a growable ring buffer.
+///
+/// Like [`crate::Vec`], `Deque<T>` defaults to the process-global allocator ([`Global`]);
+/// use [`Deque::new_in`] to back it by a custom [`Allocator`] instead.
+pub struct Deque<T, A: Allocator = Global> {
+    ptr: Option<NonNull<T>>,
+    head: usize,
+    len: usize,
+    cap: usize,
+    alloc: A,
+}
+
+impl<T> Deque<T, Global> {
+    /// Creates a new, empty `Deque` using the global allocator.
+    ///
+    /// Does not allocate until elements are pushed onto it.
+    pub fn new() -> Self {
+        Self::new_in(Global)
+    }
+}
+
+impl<T> Default for Deque<T, Global> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, A: Allocator> Deque<T, A> {
+    /// Creates a new, empty `Deque` backed by `alloc`.
+    ///
+    /// Does not allocate until elements are pushed onto it.
+    pub fn new_in(alloc: A) -> Self {
+        Self {
+            ptr: None,
+            head: 0,
+            len: 0,
+            cap: if is_zst::<T>() { usize::MAX } else { 0 },
+            alloc,
+        }
+    }
+
+    /// Returns the number of elements in the deque.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the deque contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of elements the deque can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.cap
+    }
+
+    fn as_ptr(&self) -> *mut T {
+        match self.ptr {
+            Some(ptr) => ptr.as_ptr(),
+            None => NonNull::dangling().as_ptr(),
+        }
+    }
+
+    /// Maps a logical index (`0` is the front) to a physical slot in the ring buffer.
+    ///
+    /// Computed as a branch on `index` vs. `self.cap - self.head` rather than
+    /// `(self.head + index) % self.cap`, which overflows `usize` once `self.cap` is
+    /// `usize::MAX` (the effectively-infinite capacity used for ZSTs) and `head` is large.
+    fn physical(&self, index: usize) -> usize {
+        let until_wrap = self.cap - self.head;
+        if index >= until_wrap {
+            index - until_wrap
+        } else {
+            self.head + index
+        }
+    }
+
+    /// Appends `value` to the back of the deque, growing it if necessary.
+    pub fn push_back(&mut self, value: T) {
+        if self.len == self.cap {
+            self.grow();
+        }
+        let slot = self.physical(self.len);
+        // SAFETY: `slot` is within the allocation and not currently occupied.
+        unsafe { self.as_ptr().add(slot).write(value) };
+        self.len += 1;
+    }
+
+    /// Appends `value` to the back of the deque, growing it if necessary, returning an
+    /// error instead of aborting if the allocation fails.
+    pub fn try_push_back(&mut self, value: T) -> Result<(), TryReserveError> {
+        if self.len == self.cap {
+            self.try_grow()?;
+        }
+        let slot = self.physical(self.len);
+        // SAFETY: `slot` is within the allocation and not currently occupied.
+        unsafe { self.as_ptr().add(slot).write(value) };
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Reserves capacity for at least `additional` more elements, growing the backing
+    /// allocation as `std::collections::VecDeque` does (amortized, may over-allocate),
+    /// returning an error instead of aborting if the allocation fails.
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        if self.cap - self.len >= additional {
+            return Ok(());
+        }
+        let needed = self
+            .len
+            .checked_add(additional)
+            .ok_or(TryReserveError::CapacityOverflow)?;
+        let new_cap = self.cap.max(4).saturating_mul(2).max(needed);
+        self.try_grow_to(new_cap)
+    }
+
+    /// Reserves capacity for exactly `self.len + additional` elements, without
+    /// over-allocating, returning an error instead of aborting if the allocation fails.
+    pub fn try_reserve_exact(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        if self.cap - self.len >= additional {
+            return Ok(());
+        }
+        let new_cap = self
+            .len
+            .checked_add(additional)
+            .ok_or(TryReserveError::CapacityOverflow)?;
+        self.try_grow_to(new_cap)
+    }
+
+    /// Prepends `value` to the front of the deque, growing it if necessary.
+    pub fn push_front(&mut self, value: T) {
+        if self.len == self.cap {
+            self.grow();
+        }
+        self.decrement_head();
+        // SAFETY: `self.head` is within the allocation and not currently occupied.
+        unsafe { self.as_ptr().add(self.head).write(value) };
+        self.len += 1;
+    }
+
+    /// Prepends `value` to the front of the deque, growing it if necessary, returning an
+    /// error instead of aborting if the allocation fails.
+    pub fn try_push_front(&mut self, value: T) -> Result<(), TryReserveError> {
+        if self.len == self.cap {
+            self.try_grow()?;
+        }
+        self.decrement_head();
+        // SAFETY: `self.head` is within the allocation and not currently occupied.
+        unsafe { self.as_ptr().add(self.head).write(value) };
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Decrements `head` modulo `cap` without computing `cap - 1 + head`, which would
+    /// overflow `usize` once `cap` is `usize::MAX` (the effectively-infinite capacity used
+    /// for ZSTs).
+    fn decrement_head(&mut self) {
+        self.head = if self.head == 0 {
+            self.cap - 1
+        } else {
+            self.head - 1
+        };
+    }
+
+    /// Removes the last element from the deque and returns it, or `None` if it is empty.
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        let slot = self.physical(self.len);
+        // SAFETY: `slot` held an initialized element that has not been read yet.
+        Some(unsafe { self.as_ptr().add(slot).read() })
+    }
+
+    /// Removes the first element from the deque and returns it, or `None` if it is empty.
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        let slot = self.head;
+        self.head = self.physical(1);
+        self.len -= 1;
+        // SAFETY: `slot` held an initialized element that has not been read yet.
+        Some(unsafe { self.as_ptr().add(slot).read() })
+    }
+
+    /// Grows the backing allocation, doubling the capacity (starting at 4), and
+    /// re-linearizes the elements so `head` becomes `0`.
+    fn grow(&mut self) {
+        debug_assert!(!is_zst::<T>(), "a ZST's capacity is always usize::MAX");
+        let new_cap = if self.cap == 0 { 4 } else { self.cap * 2 };
+        let new_ptr =
+            alloc_array_in::<T, A>(new_cap, &self.alloc).expect("non-zero capacity allocation");
+        // SAFETY: See `try_grow_to`.
+        unsafe { self.relinearize_into(new_ptr) };
+        self.ptr = Some(new_ptr);
+        self.cap = new_cap;
+        self.head = 0;
+    }
+
+    /// Grows the backing allocation, doubling the capacity (starting at 4), and
+    /// re-linearizes the elements so `head` becomes `0`. Returns an error instead of
+    /// aborting if the allocation fails, leaving the deque unchanged.
+    fn try_grow(&mut self) -> Result<(), TryReserveError> {
+        debug_assert!(!is_zst::<T>(), "a ZST's capacity is always usize::MAX");
+        let new_cap = if self.cap == 0 { 4 } else { self.cap.saturating_mul(2) };
+        self.try_grow_to(new_cap)
+    }
+
+    /// Grows the backing allocation to exactly `new_cap`, and re-linearizes the elements
+    /// so `head` becomes `0`. Returns an error instead of aborting if the allocation
+    /// fails, leaving the deque unchanged.
+    fn try_grow_to(&mut self, new_cap: usize) -> Result<(), TryReserveError> {
+        debug_assert!(!is_zst::<T>(), "a ZST's capacity is always usize::MAX");
+        let new_ptr = try_alloc_array_in::<T, A>(new_cap, &self.alloc)?
+            .expect("non-zero capacity allocation");
+        // SAFETY: `new_ptr` was just allocated with room for at least `new_cap >= self.len`
+        // elements, and every slot written below is read exactly once from the old buffer.
+        unsafe { self.relinearize_into(new_ptr) };
+        self.ptr = Some(new_ptr);
+        self.cap = new_cap;
+        self.head = 0;
+        Ok(())
+    }
+
+    /// Copies each live element from its (possibly wrapped) slot in the current ring
+    /// buffer into sequential slots starting at `new_ptr`, then frees the old buffer
+    /// without dropping its elements (ownership of every element moved).
+    ///
+    /// # Safety
+    ///
+    /// `new_ptr` must point to a fresh allocation with room for at least `self.len`
+    /// elements of `T`.
+    unsafe fn relinearize_into(&mut self, new_ptr: NonNull<T>) {
+        for i in 0..self.len {
+            // SAFETY: `self.physical(i)` is a previously-initialized slot, and `new_ptr`
+            // has room for `self.len` elements; the caller upholds both.
+            unsafe {
+                let src = self.as_ptr().add(self.physical(i));
+                new_ptr.as_ptr().add(i).copy_from_nonoverlapping(src, 1);
+            }
+        }
+        if let (Some(old_ptr), true) = (self.ptr, self.cap > 0) {
+            let layout = crate::ArrayLayout::<T>::new(self.cap).layout();
+            // SAFETY: `old_ptr` was allocated from `self.alloc` with this exact layout.
+            unsafe { self.alloc.deallocate(old_ptr.cast(), layout) };
+        }
+    }
+}
+
+impl<T, A: Allocator> Drop for Deque<T, A> {
+    fn drop(&mut self) {
+        while self.pop_front().is_some() {}
+        if let (Some(ptr), true) = (self.ptr, self.cap > 0) {
+            let layout = crate::ArrayLayout::<T>::new(self.cap).layout();
+            // SAFETY: `ptr` was allocated from `self.alloc` with this exact layout.
+            unsafe { self.alloc.deallocate(ptr.cast(), layout) };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Deque;
+    use crate::TryReserveError;
+
+    #[test]
+    fn try_push_and_try_reserve_succeed_and_grow_capacity() {
+        let mut d = Deque::new();
+        for i in 0..10u32 {
+            d.try_push_back(i).unwrap();
+        }
+        d.try_push_front(100).unwrap();
+        assert_eq!(d.len(), 11);
+        assert!(d.capacity() >= 11);
+        assert_eq!(d.pop_front(), Some(100));
+        for i in 0..10u32 {
+            assert_eq!(d.pop_front(), Some(i));
+        }
+
+        d.try_reserve(1000).unwrap();
+        assert!(d.capacity() >= d.len() + 1000);
+    }
+
+    #[test]
+    fn try_reserve_reports_capacity_overflow_instead_of_aborting() {
+        let mut d: Deque<u8> = Deque::new();
+        d.try_push_back(1).unwrap();
+        d.try_push_back(2).unwrap();
+        let err = d.try_reserve(usize::MAX - 1).unwrap_err();
+        assert_eq!(err, TryReserveError::CapacityOverflow);
+        // The deque is left usable after a failed reservation.
+        assert_eq!(d.len(), 2);
+        assert_eq!(d.pop_front(), Some(1));
+    }
+
+    #[test]
+    fn try_reserve_exact_reports_capacity_overflow_instead_of_aborting() {
+        let mut d: Deque<u8> = Deque::new();
+        d.try_push_back(1).unwrap();
+        let err = d.try_reserve_exact(usize::MAX).unwrap_err();
+        assert_eq!(err, TryReserveError::CapacityOverflow);
+    }
+
+    #[test]
+    fn push_and_pop_from_both_ends() {
+        let mut d = Deque::new();
+        assert_eq!(d.capacity(), 0);
+        for i in 0..10u32 {
+            d.push_back(i);
+        }
+        for i in (0..5u32).rev() {
+            d.push_front(i);
+        }
+        // Front to back: 0, 1, 2, 3, 4, 0, 1, ..., 9
+        assert_eq!(d.len(), 15);
+        for i in 0..5u32 {
+            assert_eq!(d.pop_front(), Some(i));
+        }
+        for i in (0..10u32).rev() {
+            assert_eq!(d.pop_back(), Some(i));
+        }
+        assert_eq!(d.pop_front(), None);
+        assert_eq!(d.pop_back(), None);
+    }
+
+    #[test]
+    fn grow_reallocates_and_preserves_order_across_wraparound() {
+        let mut d = Deque::new();
+        // Fill and drain once so `head` wraps around before the buffer grows,
+        // then push past the current capacity to force a reallocation.
+        for i in 0..3u32 {
+            d.push_back(i);
+        }
+        for _ in 0..3 {
+            d.pop_front();
+        }
+        for i in 0..1000u32 {
+            d.push_back(i);
+        }
+        assert_eq!(d.len(), 1000);
+        for i in 0..1000u32 {
+            assert_eq!(d.pop_front(), Some(i));
+        }
+        assert_eq!(d.pop_front(), None);
+    }
+
+    #[test]
+    fn zst_physical_does_not_overflow_after_push_front() {
+        // `push_front` moves `head` down towards `usize::MAX` (the ZST capacity), so a
+        // later `push_back`/`pop_back` computing a physical index past that point must not
+        // overflow.
+        let mut d: Deque<[u8; 0]> = Deque::new();
+        d.push_front([]);
+        d.push_back([]);
+        d.push_back([]);
+        assert_eq!(d.len(), 3);
+        assert_eq!(d.pop_back(), Some([]));
+        assert_eq!(d.pop_back(), Some([]));
+        assert_eq!(d.pop_back(), Some([]));
+        assert_eq!(d.pop_back(), None);
+    }
+
+    #[test]
+    fn zst_push_pop_never_allocates() {
+        let mut d: Deque<[u8; 0]> = Deque::new();
+        assert_eq!(d.capacity(), usize::MAX);
+        for _ in 0..1000 {
+            d.push_back([]);
+        }
+        for _ in 0..500 {
+            d.push_front([]);
+        }
+        assert_eq!(d.len(), 1500);
+        for _ in 0..1500 {
+            assert_eq!(d.pop_front(), Some([]));
+        }
+        assert_eq!(d.pop_back(), None);
+    }
+}
@@ -6,6 +6,16 @@
 //!
 //! Currently implemented:
 //! - `Vec`
+//! - `Deque`
+//! - `LinkedList`
+//!
+//! All three collections are generic over an [`Allocator`], mirroring the design of
+//! `std`'s (currently unstable) allocator API: they default to [`Global`], but any type
+//! implementing [`Allocator`] (an arena, a bump allocator, ...) can be plugged in via the
+//! `_in` constructors.
+
+use core::alloc::Layout;
+use core::ptr::NonNull;
 
 pub mod deque;
 pub mod linked_list;
@@ -15,55 +25,367 @@ pub use deque::Deque;
 pub use linked_list::LinkedList;
 pub use vec::Vec;
 
-/// Allocates enough memory for [T; size].
-/// 
+/// The error type returned by [`Allocator`] methods when an allocation request cannot be
+/// satisfied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AllocError;
+
+impl core::fmt::Display for AllocError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("memory allocation failed")
+    }
+}
+
+impl std::error::Error for AllocError {}
+
+/// A source and destination of memory, mirroring the allocator-wg `Allocator` trait.
+///
+/// Implementing this trait lets a collection be backed by something other than the
+/// process-global allocator, e.g. an arena or a bump allocator.
+///
+/// # Safety
+///
+/// Implementations must return a pointer to a valid, uniquely-owned allocation of at
+/// least `layout.size()` bytes, aligned to `layout.align()`. `grow`/`shrink` must only be
+/// called with a `ptr`/`old_layout` pair previously returned by this same allocator.
+pub unsafe trait Allocator {
+    /// Attempts to allocate a block of memory described by `layout`.
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError>;
+
+    /// Deallocates the memory referenced by `ptr`, which must have been allocated by this
+    /// allocator using a layout equal to `layout`.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must denote a block of memory currently allocated via this allocator, and
+    /// `layout` must match the layout it was allocated with.
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout);
+
+    /// Attempts to extend the allocation at `ptr` from `old_layout` to `new_layout`.
+    ///
+    /// The default implementation allocates a new block, copies `old_layout.size()` bytes
+    /// over, and deallocates the old block.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must denote a block of memory currently allocated via this allocator with
+    /// `old_layout`, and `new_layout.size() >= old_layout.size()`.
+    unsafe fn grow(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        debug_assert!(new_layout.size() >= old_layout.size());
+        let new = self.allocate(new_layout)?;
+        core::ptr::copy_nonoverlapping(ptr.as_ptr(), new.as_ptr() as *mut u8, old_layout.size());
+        self.deallocate(ptr, old_layout);
+        Ok(new)
+    }
+
+    /// Attempts to shrink the allocation at `ptr` from `old_layout` to `new_layout`.
+    ///
+    /// The default implementation allocates a new block, copies `new_layout.size()` bytes
+    /// over, and deallocates the old block.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must denote a block of memory currently allocated via this allocator with
+    /// `old_layout`, and `new_layout.size() <= old_layout.size()`.
+    unsafe fn shrink(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        debug_assert!(new_layout.size() <= old_layout.size());
+        let new = self.allocate(new_layout)?;
+        core::ptr::copy_nonoverlapping(ptr.as_ptr(), new.as_ptr() as *mut u8, new_layout.size());
+        self.deallocate(ptr, old_layout);
+        Ok(new)
+    }
+
+    /// Like [`allocate`](Allocator::allocate), but guarantees the returned memory is
+    /// zero-initialized.
+    ///
+    /// The default implementation allocates normally and then writes zeros over it;
+    /// implementors backed by the OS (see [`Global`]) should override this to hand back
+    /// already-zeroed pages instead of allocating then memset-ing.
+    fn allocate_zeroed(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let ptr = self.allocate(layout)?;
+        // SAFETY: `allocate` just handed back this block, so writing `layout.size()`
+        // zero bytes into it is in bounds.
+        unsafe { ptr.cast::<u8>().as_ptr().write_bytes(0, layout.size()) };
+        Ok(ptr)
+    }
+}
+
+/// The process-global allocator, backed by `std::alloc`.
+///
+/// This is the default [`Allocator`] used by [`Vec`], [`Deque`] and [`LinkedList`] when no
+/// other allocator is specified.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Global;
+
+/// Turns a null/non-null `*mut u8` returned by a `std::alloc` function into the
+/// `Result<NonNull<[u8]>, AllocError>` shape the [`Allocator`] trait expects.
+fn alloc_result(ptr: *mut u8, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+    let ptr = NonNull::new(ptr).ok_or(AllocError)?;
+    Ok(NonNull::slice_from_raw_parts(ptr, layout.size()))
+}
+
+// SAFETY: Delegates directly to `std::alloc`, which upholds the `Allocator` contract.
+unsafe impl Allocator for Global {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let ptr = if layout.size() == 0 {
+            layout.align() as *mut u8
+        } else {
+            unsafe { std::alloc::alloc(layout) }
+        };
+        alloc_result(ptr, layout)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        if layout.size() != 0 {
+            unsafe { std::alloc::dealloc(ptr.as_ptr(), layout) };
+        }
+    }
+
+    unsafe fn grow(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, AllocError> {
+        if old_layout.size() == 0 {
+            return self.allocate(new_layout);
+        }
+        let raw = unsafe { std::alloc::realloc(ptr.as_ptr(), old_layout, new_layout.size()) };
+        alloc_result(raw, new_layout)
+    }
+
+    fn allocate_zeroed(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let ptr = if layout.size() == 0 {
+            layout.align() as *mut u8
+        } else {
+            // Lets the allocator hand back already-zeroed pages instead of allocating
+            // then memset-ing, which matters for large buffers.
+            unsafe { std::alloc::alloc_zeroed(layout) }
+        };
+        alloc_result(ptr, layout)
+    }
+}
+
+/// The error type returned by the fallible `try_reserve`/`try_push` family of methods.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TryReserveError {
+    /// The requested capacity exceeds `isize::MAX` bytes, or overflows `usize` while
+    /// computing the required layout.
+    CapacityOverflow,
+    /// The allocator reported that the request could not be satisfied.
+    AllocError {
+        /// The layout that the allocator failed to provide.
+        layout: Layout,
+    },
+}
+
+impl core::fmt::Display for TryReserveError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::CapacityOverflow => f.write_str("capacity overflow"),
+            Self::AllocError { layout } => write!(
+                f,
+                "memory allocation of {} bytes failed",
+                layout.size()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for TryReserveError {}
+
+/// Allocates enough memory for `[T; size]` using `alloc`, aborting the process on failure.
+///
+/// Returns `None` if `T` is a ZST (zero-sized type) or if `size == 0`.
+pub(crate) fn alloc_array_in<T, A: Allocator>(size: usize, alloc: &A) -> Option<NonNull<T>> {
+    match try_alloc_array_in(size, alloc) {
+        Ok(ptr) => ptr,
+        Err(TryReserveError::CapacityOverflow) => capacity_overflow(),
+        Err(TryReserveError::AllocError { layout }) => std::alloc::handle_alloc_error(layout),
+    }
+}
+
+/// Allocates enough zero-initialized memory for `[T; size]` using `alloc`, aborting the
+/// process on failure.
+///
+/// Prefer this over [`alloc_array_in`] followed by a manual zeroing pass: [`Global`] hands
+/// back already-zeroed pages instead of allocating then memset-ing.
+///
 /// Returns `None` if `T` is a ZST (zero-sized type) or if `size == 0`.
-pub(crate) fn alloc_array<T>(size: usize) -> Option<core::ptr::NonNull<T>> {
-    if size * core::mem::size_of::<T>() == 0 {
+pub(crate) fn alloc_zeroed_array_in<T, A: Allocator>(size: usize, alloc: &A) -> Option<NonNull<T>> {
+    if size == 0 || is_zst::<T>() {
         return None;
     }
-    let layout = array_layout::<T>(size);
-    let alloc = unsafe { std::alloc::alloc(layout) };
-    if alloc.is_null() {
-        std::alloc::handle_alloc_error(layout);
+    let layout = ArrayLayout::<T>::new(size).layout();
+    match alloc.allocate_zeroed(layout) {
+        Ok(ptr) => Some(ptr.cast()),
+        Err(_) => std::alloc::handle_alloc_error(layout),
     }
-    unsafe { Some(core::ptr::NonNull::new_unchecked(alloc as *mut T)) }
 }
-/// Reallocates memory from `[old_ptr; old_layout]` to `[T; new_size]`.
-/// 
-/// If `old_size == 0`, allocates memory for [T; new_size] without reallocating.
-/// 
+
+/// Reallocates memory from `[old_ptr; old_size]` to `[T; new_size]` using `alloc`,
+/// aborting the process on failure.
+///
+/// If `old_size == 0`, allocates memory for `[T; new_size]` without reallocating.
+///
 /// Returns `None` if `T` is a ZST (zero-sized type) or if `size == 0`.
-pub(crate) fn realloc_array<T>(
-    old_ptr: core::ptr::NonNull<T>,
+pub(crate) fn realloc_array_in<T, A: Allocator>(
+    old_ptr: NonNull<T>,
     old_size: usize,
     new_size: usize,
-) -> Option<core::ptr::NonNull<T>> {
-    if new_size * core::mem::size_of::<T>() == 0 {
-        return None;
+    alloc: &A,
+) -> Option<NonNull<T>> {
+    match try_realloc_array_in(old_ptr, old_size, new_size, alloc) {
+        Ok(ptr) => ptr,
+        Err(TryReserveError::CapacityOverflow) => capacity_overflow(),
+        Err(TryReserveError::AllocError { layout }) => std::alloc::handle_alloc_error(layout),
+    }
+}
+
+/// Allocates enough memory for `[T; size]` using `alloc`, reporting failure instead of
+/// aborting.
+///
+/// Returns `Ok(None)` if `T` is a ZST (zero-sized type) or if `size == 0`.
+pub(crate) fn try_alloc_array_in<T, A: Allocator>(
+    size: usize,
+    alloc: &A,
+) -> Result<Option<NonNull<T>>, TryReserveError> {
+    if size == 0 || is_zst::<T>() {
+        return Ok(None);
+    }
+    let layout = ArrayLayout::<T>::try_new(size)
+        .ok_or(TryReserveError::CapacityOverflow)?
+        .layout();
+    alloc
+        .allocate(layout)
+        .map(|ptr| Some(ptr.cast()))
+        .map_err(|_| TryReserveError::AllocError { layout })
+}
+
+/// Reallocates memory from `[old_ptr; old_size]` to `[T; new_size]` using `alloc`,
+/// reporting failure instead of aborting.
+///
+/// If `old_size == 0`, allocates memory for `[T; new_size]` without reallocating.
+///
+/// Returns `Ok(None)` if `T` is a ZST (zero-sized type) or if `new_size == 0`.
+pub(crate) fn try_realloc_array_in<T, A: Allocator>(
+    old_ptr: NonNull<T>,
+    old_size: usize,
+    new_size: usize,
+    alloc: &A,
+) -> Result<Option<NonNull<T>>, TryReserveError> {
+    if new_size == 0 || is_zst::<T>() {
+        return Ok(None);
     }
-    let new_layout = array_layout::<T>(new_size);
-    // SAFETY: 
-    let alloc = if old_size == 0 {
-        unsafe { std::alloc::alloc(new_layout) }
+    let new_layout = ArrayLayout::<T>::try_new(new_size)
+        .ok_or(TryReserveError::CapacityOverflow)?
+        .layout();
+    let result = if old_size == 0 {
+        alloc.allocate(new_layout)
     } else {
-        let old_ptr = old_ptr.as_ptr() as *mut u8;
-        let old_layout = array_layout::<T>(old_size);
-        unsafe { std::alloc::realloc(old_ptr, old_layout, new_layout.size()) }
+        let old_ptr = old_ptr.cast::<u8>();
+        let old_layout = ArrayLayout::<T>::new(old_size).layout();
+        unsafe { alloc.grow(old_ptr, old_layout, new_layout) }
     };
-    if alloc.is_null() {
-        std::alloc::handle_alloc_error(new_layout);
-    }
-    // SAFETY: Size and alignment are correct, pointer is not null
-    unsafe { Some(core::ptr::NonNull::new_unchecked(alloc as *mut T)) }
+    result
+        .map(|ptr| Some(ptr.cast()))
+        .map_err(|_| TryReserveError::AllocError { layout: new_layout })
 }
 
-pub(crate) const fn array_layout<T>(size: usize) -> core::alloc::Layout {
-    // SAFETY: The size and alignment are correct.
-    unsafe {
-        core::alloc::Layout::from_size_align_unchecked(
-            core::mem::size_of::<T>() * size,
-            core::mem::align_of::<T>(),
-        )
+/// The `Layout` for `[T; count]`, computed with overflow checks.
+///
+/// Because `T` is fixed at construction, once built the layout is known to describe a
+/// valid allocation request: `size_of::<T>() * count` neither overflows `usize` nor
+/// exceeds `isize::MAX`, so the alignment is trivially valid and the size is safe to pass
+/// to `Layout::from_size_align_unchecked`.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ArrayLayout<T> {
+    layout: Layout,
+    _marker: core::marker::PhantomData<T>,
+}
+
+impl<T> ArrayLayout<T> {
+    /// Computes the layout for `[T; count]`.
+    ///
+    /// # Panics
+    ///
+    /// Panics with "capacity overflow" (mirroring `std`'s `Vec`) if
+    /// `size_of::<T>() * count` overflows `usize` or the result exceeds `isize::MAX`.
+    pub(crate) fn new(count: usize) -> Self {
+        match Self::try_new(count) {
+            Some(layout) => layout,
+            None => capacity_overflow(),
+        }
+    }
+
+    /// Computes the layout for `[T; count]`, returning `None` instead of panicking if the
+    /// size overflows `usize` or exceeds `isize::MAX`.
+    pub(crate) fn try_new(count: usize) -> Option<Self> {
+        let size = core::mem::size_of::<T>().checked_mul(count)?;
+        if size > isize::MAX as usize {
+            return None;
+        }
+        // SAFETY: `size` was just checked to fit in an `isize`, and `align_of::<T>()` is
+        // always a valid, non-zero power of two.
+        let layout = unsafe {
+            Layout::from_size_align_unchecked(size, core::mem::align_of::<T>())
+        };
+        Some(Self {
+            layout,
+            _marker: core::marker::PhantomData,
+        })
     }
+
+    /// Returns the underlying `Layout`.
+    pub(crate) fn layout(&self) -> Layout {
+        self.layout
+    }
+}
+
+/// Panics with the same "capacity overflow" message `std::vec::Vec` uses when a
+/// reservation request cannot be represented.
+pub(crate) fn capacity_overflow() -> ! {
+    panic!("capacity overflow");
 }
+
+/// Returns `true` if `T` is a zero-sized type.
+///
+/// Deliberately checked as `size == 0 || size_of::<T>() == 0` rather than
+/// `size * size_of::<T>() == 0` at call sites: the product overflows `usize` and can wrap
+/// to exactly `0` for a large `size` with a non-trivial `size_of::<T>()`, which would
+/// otherwise make a legitimate huge allocation request look like "nothing to allocate".
+pub(crate) fn is_zst<T>() -> bool {
+    core::mem::size_of::<T>() == 0
+}
+
+/// Marker trait for types whose all-zero-bytes bit pattern is a valid value.
+///
+/// Implemented for the primitive numeric types, `bool`, and arrays of `Zeroable` types.
+/// This gates [`Vec::with_zeroed`](vec::Vec::with_zeroed), which fills a buffer via
+/// [`alloc_zeroed_array_in`] rather than writing each element individually.
+///
+/// # Safety
+///
+/// Implementors must guarantee that a value of `Self` consisting entirely of zero bytes
+/// is valid: no padding bytes, niches, or discriminants may assume a non-zero pattern.
+pub unsafe trait Zeroable {}
+
+macro_rules! impl_zeroable {
+    ($($t:ty),* $(,)?) => {
+        $( unsafe impl Zeroable for $t {} )*
+    };
+}
+
+impl_zeroable!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64, bool);
+
+unsafe impl<T: Zeroable, const N: usize> Zeroable for [T; N] {}
@@ -0,0 +1,203 @@
+//! A doubly linked list, similar to [`std::collections::LinkedList`].
+
+use core::alloc::Layout;
+use core::ptr::NonNull;
+
+use crate::{Allocator, Global};
+
+struct Node<T> {
+    value: T,
+    next: Option<NonNull<Node<T>>>,
+    prev: Option<NonNull<Node<T>>>,
+}
+
+/// A doubly linked list.
+///
+/// Like [`crate::Vec`], `LinkedList<T>` defaults to the process-global allocator
+/// ([`Global`]); use [`LinkedList::new_in`] to back it by a custom [`Allocator`] instead.
+/// Unlike `Vec`/`Deque`, each element is allocated individually as its own [`Node`].
+pub struct LinkedList<T, A: Allocator = Global> {
+    head: Option<NonNull<Node<T>>>,
+    tail: Option<NonNull<Node<T>>>,
+    len: usize,
+    alloc: A,
+}
+
+impl<T> LinkedList<T, Global> {
+    /// Creates a new, empty `LinkedList` using the global allocator.
+    pub fn new() -> Self {
+        Self::new_in(Global)
+    }
+}
+
+impl<T> Default for LinkedList<T, Global> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, A: Allocator> LinkedList<T, A> {
+    /// Creates a new, empty `LinkedList` backed by `alloc`.
+    pub fn new_in(alloc: A) -> Self {
+        Self {
+            head: None,
+            tail: None,
+            len: 0,
+            alloc,
+        }
+    }
+
+    /// Returns the number of elements in the list.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the list contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn node_layout() -> Layout {
+        Layout::new::<Node<T>>()
+    }
+
+    /// Allocates a single node holding `value`, with `next`/`prev` left unset.
+    fn alloc_node(&self, value: T) -> NonNull<Node<T>> {
+        let layout = Self::node_layout();
+        let ptr = match self.alloc.allocate(layout) {
+            Ok(ptr) => ptr.cast::<Node<T>>(),
+            Err(_) => std::alloc::handle_alloc_error(layout),
+        };
+        // SAFETY: `ptr` points to a fresh, uninitialized, correctly-sized and aligned
+        // allocation.
+        unsafe {
+            ptr.as_ptr().write(Node {
+                value,
+                next: None,
+                prev: None,
+            })
+        };
+        ptr
+    }
+
+    /// Deallocates a single node previously returned by `alloc_node`. Does not run the
+    /// node's value's destructor; callers must have already moved it out.
+    unsafe fn dealloc_node(&self, node: NonNull<Node<T>>) {
+        let layout = Self::node_layout();
+        // SAFETY: `node` was allocated from `self.alloc` with this exact layout.
+        unsafe { self.alloc.deallocate(node.cast(), layout) };
+    }
+
+    /// Appends `value` to the back of the list.
+    pub fn push_back(&mut self, value: T) {
+        let node = self.alloc_node(value);
+        match self.tail {
+            // SAFETY: `tail` points to a live, exclusively-owned node.
+            Some(mut tail) => unsafe {
+                tail.as_mut().next = Some(node);
+                node.as_ptr().as_mut().unwrap().prev = Some(tail);
+            },
+            None => self.head = Some(node),
+        }
+        self.tail = Some(node);
+        self.len += 1;
+    }
+
+    /// Prepends `value` to the front of the list.
+    pub fn push_front(&mut self, value: T) {
+        let node = self.alloc_node(value);
+        match self.head {
+            // SAFETY: `head` points to a live, exclusively-owned node.
+            Some(mut head) => unsafe {
+                head.as_mut().prev = Some(node);
+                node.as_ptr().as_mut().unwrap().next = Some(head);
+            },
+            None => self.tail = Some(node),
+        }
+        self.head = Some(node);
+        self.len += 1;
+    }
+
+    /// Removes the last element from the list and returns it, or `None` if it is empty.
+    pub fn pop_back(&mut self) -> Option<T> {
+        let tail = self.tail?;
+        // SAFETY: `tail` points to a live node owned by this list; it is unlinked and
+        // freed below, so no dangling references remain.
+        unsafe {
+            self.tail = tail.as_ref().prev;
+            match self.tail {
+                Some(mut new_tail) => new_tail.as_mut().next = None,
+                None => self.head = None,
+            }
+            let node = tail.as_ptr().read();
+            self.dealloc_node(tail);
+            self.len -= 1;
+            Some(node.value)
+        }
+    }
+
+    /// Removes the first element from the list and returns it, or `None` if it is empty.
+    pub fn pop_front(&mut self) -> Option<T> {
+        let head = self.head?;
+        // SAFETY: `head` points to a live node owned by this list; it is unlinked and
+        // freed below, so no dangling references remain.
+        unsafe {
+            self.head = head.as_ref().next;
+            match self.head {
+                Some(mut new_head) => new_head.as_mut().prev = None,
+                None => self.tail = None,
+            }
+            let node = head.as_ptr().read();
+            self.dealloc_node(head);
+            self.len -= 1;
+            Some(node.value)
+        }
+    }
+}
+
+impl<T, A: Allocator> Drop for LinkedList<T, A> {
+    fn drop(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LinkedList;
+
+    #[test]
+    fn push_and_pop_from_both_ends() {
+        let mut l = LinkedList::new();
+        assert!(l.is_empty());
+        for i in 0..10u32 {
+            l.push_back(i);
+        }
+        for i in (0..5u32).rev() {
+            l.push_front(i);
+        }
+        // Front to back: 0, 1, 2, 3, 4, 0, 1, ..., 9
+        assert_eq!(l.len(), 15);
+        for i in 0..5u32 {
+            assert_eq!(l.pop_front(), Some(i));
+        }
+        for i in (0..10u32).rev() {
+            assert_eq!(l.pop_back(), Some(i));
+        }
+        assert_eq!(l.pop_front(), None);
+        assert_eq!(l.pop_back(), None);
+        assert!(l.is_empty());
+    }
+
+    #[test]
+    fn many_pushes_and_pops_preserve_order() {
+        let mut l = LinkedList::new();
+        for i in 0..1000u64 {
+            l.push_back(i);
+        }
+        assert_eq!(l.len(), 1000);
+        for i in 0..1000u64 {
+            assert_eq!(l.pop_front(), Some(i));
+        }
+        assert_eq!(l.pop_front(), None);
+    }
+}
@@ -0,0 +1,372 @@
+//! A contiguous growable array type, similar to [`std::vec::Vec`].
+
+use core::ptr::NonNull;
+
+use crate::{
+    alloc_array_in, alloc_zeroed_array_in, is_zst, realloc_array_in, try_alloc_array_in,
+    try_realloc_array_in, Allocator, Global, TryReserveError, Zeroable,
+};
+
+/// A contiguous growable array type, written as `Vec<T, A>`.
+///
+/// Like its `std` counterpart, `Vec<T>` defaults to the process-global allocator
+/// ([`Global`]); use [`Vec::new_in`] to back it by a custom [`Allocator`] instead.
+pub struct Vec<T, A: Allocator = Global> {
+    ptr: Option<NonNull<T>>,
+    len: usize,
+    cap: usize,
+    alloc: A,
+}
+
+impl<T> Vec<T, Global> {
+    /// Creates a new, empty `Vec` using the global allocator.
+    ///
+    /// Does not allocate until elements are pushed onto it.
+    pub fn new() -> Self {
+        Self::new_in(Global)
+    }
+
+    /// Creates a new, empty `Vec` with space for at least `capacity` elements, using the
+    /// global allocator.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::with_capacity_in(capacity, Global)
+    }
+}
+
+impl<T> Default for Vec<T, Global> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Zeroable> Vec<T, Global> {
+    /// Creates a new `Vec` of length `len` whose elements are all zero-initialized, using
+    /// the global allocator.
+    ///
+    /// This is faster than allocating and then writing zeros element-by-element, since
+    /// [`Global`] can hand back already-zeroed memory straight from the allocator.
+    pub fn with_zeroed(len: usize) -> Self {
+        Self::with_zeroed_in(len, Global)
+    }
+}
+
+impl<T: Zeroable, A: Allocator> Vec<T, A> {
+    /// Creates a new `Vec` of length `len` whose elements are all zero-initialized, backed
+    /// by `alloc`.
+    pub fn with_zeroed_in(len: usize, alloc: A) -> Self {
+        let ptr = if len == 0 || is_zst::<T>() {
+            None
+        } else {
+            alloc_zeroed_array_in::<T, A>(len, &alloc)
+        };
+        Self {
+            ptr,
+            len,
+            cap: if is_zst::<T>() { usize::MAX } else { len },
+            alloc,
+        }
+    }
+}
+
+impl<T, A: Allocator> Vec<T, A> {
+    /// Creates a new, empty `Vec` backed by `alloc`.
+    ///
+    /// Does not allocate until elements are pushed onto it.
+    pub fn new_in(alloc: A) -> Self {
+        Self {
+            ptr: None,
+            len: 0,
+            cap: if is_zst::<T>() { usize::MAX } else { 0 },
+            alloc,
+        }
+    }
+
+    /// Creates a new, empty `Vec` with space for at least `capacity` elements, backed by
+    /// `alloc`.
+    pub fn with_capacity_in(capacity: usize, alloc: A) -> Self {
+        let mut vec = Self::new_in(alloc);
+        if capacity > 0 && !is_zst::<T>() {
+            vec.ptr = alloc_array_in::<T, A>(capacity, &vec.alloc);
+            vec.cap = capacity;
+        }
+        vec
+    }
+
+    /// Returns the number of elements in the vector.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the vector contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of elements the vector can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.cap
+    }
+
+    /// Returns a raw pointer to the vector's buffer.
+    fn as_ptr(&self) -> *mut T {
+        match self.ptr {
+            Some(ptr) => ptr.as_ptr(),
+            None => NonNull::dangling().as_ptr(),
+        }
+    }
+
+    /// Appends `value` to the back of the vector, growing it if necessary.
+    ///
+    /// # Panics
+    ///
+    /// Aborts the process on allocation failure, or panics with "capacity overflow" if the
+    /// required capacity cannot be represented.
+    pub fn push(&mut self, value: T) {
+        if self.len == self.cap {
+            self.grow();
+        }
+        // SAFETY: `self.len < self.cap`, so the slot at `self.len` is within the
+        // allocation and not yet initialized.
+        unsafe { self.as_ptr().add(self.len).write(value) };
+        self.len += 1;
+    }
+
+    /// Appends `value` to the back of the vector, growing it if necessary, returning an
+    /// error instead of aborting if the allocation fails.
+    pub fn try_push(&mut self, value: T) -> Result<(), TryReserveError> {
+        if self.len == self.cap {
+            self.try_grow(1)?;
+        }
+        // SAFETY: `self.len < self.cap`, so the slot at `self.len` is within the
+        // allocation and not yet initialized.
+        unsafe { self.as_ptr().add(self.len).write(value) };
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Reserves capacity for at least `additional` more elements, growing the backing
+    /// allocation as `std::vec::Vec` does (amortized, may over-allocate), returning an
+    /// error instead of aborting if the allocation fails.
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        if self.cap - self.len >= additional {
+            return Ok(());
+        }
+        self.try_grow(additional)
+    }
+
+    /// Reserves capacity for exactly `self.len + additional` elements, without
+    /// over-allocating, returning an error instead of aborting if the allocation fails.
+    pub fn try_reserve_exact(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        if self.cap - self.len >= additional {
+            return Ok(());
+        }
+        let new_cap = self
+            .len
+            .checked_add(additional)
+            .ok_or(TryReserveError::CapacityOverflow)?;
+        self.ptr = match self.ptr {
+            None => try_alloc_array_in::<T, A>(new_cap, &self.alloc)?,
+            Some(ptr) => try_realloc_array_in::<T, A>(ptr, self.cap, new_cap, &self.alloc)?,
+        };
+        self.cap = new_cap;
+        Ok(())
+    }
+
+    /// Removes the last element from the vector and returns it, or `None` if it is empty.
+    pub fn pop(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        // SAFETY: `self.len` now indexes a previously-initialized element that has not
+        // been read yet.
+        Some(unsafe { self.as_ptr().add(self.len).read() })
+    }
+
+    /// Returns a reference to the element at `index`, or `None` if out of bounds.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.len {
+            return None;
+        }
+        // SAFETY: `index < self.len`, so this slot is initialized.
+        Some(unsafe { &*self.as_ptr().add(index) })
+    }
+
+    /// Grows the backing allocation, doubling the capacity (starting at 4).
+    ///
+    /// # Panics
+    ///
+    /// Aborts the process on allocation failure, or panics with "capacity overflow" if the
+    /// required capacity cannot be represented.
+    fn grow(&mut self) {
+        debug_assert!(!is_zst::<T>(), "a ZST's capacity is always usize::MAX");
+        let new_cap = if self.cap == 0 { 4 } else { self.cap * 2 };
+        self.ptr = match self.ptr {
+            None => alloc_array_in::<T, A>(new_cap, &self.alloc),
+            Some(ptr) => realloc_array_in::<T, A>(ptr, self.cap, new_cap, &self.alloc),
+        };
+        self.cap = new_cap;
+    }
+
+    /// Grows the backing allocation to hold at least `self.len + additional` elements,
+    /// doubling the capacity (starting at 4) as `std::vec::Vec` does, returning an error
+    /// instead of aborting if the allocation fails.
+    fn try_grow(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        let needed = self
+            .len
+            .checked_add(additional)
+            .ok_or(TryReserveError::CapacityOverflow)?;
+        let new_cap = self.cap.saturating_mul(2).max(needed).max(4);
+        self.ptr = match self.ptr {
+            None => try_alloc_array_in::<T, A>(new_cap, &self.alloc)?,
+            Some(ptr) => try_realloc_array_in::<T, A>(ptr, self.cap, new_cap, &self.alloc)?,
+        };
+        self.cap = new_cap;
+        Ok(())
+    }
+}
+
+impl<T, A: Allocator> Drop for Vec<T, A> {
+    fn drop(&mut self) {
+        while self.pop().is_some() {}
+        if let (Some(ptr), true) = (self.ptr, self.cap > 0) {
+            let layout = crate::ArrayLayout::<T>::new(self.cap).layout();
+            // SAFETY: `ptr` was allocated from `self.alloc` with this exact layout.
+            unsafe { self.alloc.deallocate(ptr.cast(), layout) };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Vec;
+    use crate::TryReserveError;
+
+    #[test]
+    fn try_push_and_try_reserve_succeed_and_grow_capacity() {
+        let mut v = Vec::new();
+        for i in 0..10u32 {
+            v.try_push(i).unwrap();
+        }
+        assert_eq!(v.len(), 10);
+        assert!(v.capacity() >= 10);
+        for i in 0..10u32 {
+            assert_eq!(v.get(i as usize), Some(&i));
+        }
+
+        v.try_reserve(1000).unwrap();
+        assert!(v.capacity() >= v.len() + 1000);
+    }
+
+    #[test]
+    fn try_reserve_reports_capacity_overflow_instead_of_aborting() {
+        let mut v: Vec<u8> = Vec::new();
+        v.try_push(1).unwrap();
+        v.try_push(2).unwrap();
+        let err = v.try_reserve(usize::MAX - 1).unwrap_err();
+        assert_eq!(err, TryReserveError::CapacityOverflow);
+        // The vector is left usable after a failed reservation.
+        assert_eq!(v.len(), 2);
+        assert_eq!(v.get(0), Some(&1));
+    }
+
+    #[test]
+    fn try_reserve_exact_reports_capacity_overflow_instead_of_aborting() {
+        let mut v: Vec<u8> = Vec::new();
+        v.try_push(1).unwrap();
+        let err = v.try_reserve_exact(usize::MAX).unwrap_err();
+        assert_eq!(err, TryReserveError::CapacityOverflow);
+    }
+
+    #[test]
+    fn push_pop_and_get_behave_like_a_growable_array() {
+        let mut v = Vec::new();
+        assert_eq!(v.capacity(), 0);
+        for i in 0..20u32 {
+            v.push(i);
+        }
+        assert_eq!(v.len(), 20);
+        assert!(v.capacity() >= 20);
+        for i in 0..20u32 {
+            assert_eq!(v.get(i as usize), Some(&i));
+        }
+        assert_eq!(v.get(20), None);
+
+        for i in (0..20u32).rev() {
+            assert_eq!(v.pop(), Some(i));
+        }
+        assert_eq!(v.pop(), None);
+        assert!(v.is_empty());
+    }
+
+    #[test]
+    fn with_capacity_preallocates_without_changing_len() {
+        let v = Vec::<u32>::with_capacity(64);
+        assert_eq!(v.len(), 0);
+        assert!(v.capacity() >= 64);
+    }
+
+    #[test]
+    fn grow_reallocates_and_preserves_existing_elements() {
+        let mut v = Vec::new();
+        // Push enough elements to force several reallocations, and check the values
+        // survive each one.
+        for i in 0..1000u64 {
+            v.push(i);
+        }
+        for i in 0..1000u64 {
+            assert_eq!(v.get(i as usize), Some(&i));
+        }
+    }
+
+    #[test]
+    fn with_zeroed_produces_correctly_zeroed_elements() {
+        let v = Vec::<u64>::with_zeroed(8);
+        assert_eq!(v.len(), 8);
+        assert_eq!(v.capacity(), 8);
+        for i in 0..8 {
+            assert_eq!(v.get(i), Some(&0u64));
+        }
+    }
+
+    #[test]
+    fn zst_push_pop_never_allocates() {
+        let mut v: Vec<()> = Vec::new();
+        assert_eq!(v.capacity(), usize::MAX);
+        for _ in 0..1000 {
+            v.push(());
+        }
+        assert_eq!(v.len(), 1000);
+        for _ in 0..1000 {
+            assert_eq!(v.pop(), Some(()));
+        }
+        assert_eq!(v.pop(), None);
+    }
+
+    #[test]
+    fn zst_drop_count_matches_logical_length() {
+        use core::sync::atomic::{AtomicUsize, Ordering};
+
+        static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+        // A ZST whose `Drop` impl has an observable side effect, so the test can tell
+        // the logical length (tracked purely via `len`, since there is no backing
+        // allocation) apart from "nothing was ever dropped".
+        struct DropSideEffect;
+        impl Drop for DropSideEffect {
+            fn drop(&mut self) {
+                DROPS.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        {
+            let mut v = Vec::new();
+            for _ in 0..5 {
+                v.push(DropSideEffect);
+            }
+            v.pop();
+            assert_eq!(DROPS.load(Ordering::SeqCst), 1);
+        }
+        assert_eq!(DROPS.load(Ordering::SeqCst), 5);
+    }
+}